@@ -1,90 +1,77 @@
-pub fn final_mul_line(a: &[f64], b: &[f64]) -> Option<Vec<f64>> {
-    // verificar que os dados são aceites e inicializar as variáveis
-
-    if a.len() != b.len() {
-        return None;
-    }
-
-    let length = a.len();
-    let side_f64 = (length as f64).sqrt();
-
-    if side_f64.fract() != 0.0 {
+use num_traits::Zero;
+use std::ops::{Add, Mul};
+
+/// `a` is `m x k`, `b` is `k x n`, and the result is `m x n`.
+pub fn final_mul_line<T>(a: &[T], b: &[T], m: usize, k: usize, n: usize) -> Option<Vec<T>>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    if a.len() != m * k || b.len() != k * n {
         return None;
     }
 
-    let side = side_f64 as usize;
-    let mut res = vec![0.0; length];
+    let mut res = vec![T::zero(); m * n];
 
-    // fazer a transposta do B para melhorar a localidade da cache
-    let mut b_transposed = vec![0.0; length];
+    // fazer a transposta do B (k x n) para (n x k) para melhorar a localidade da cache
+    let mut b_transposed = vec![T::zero(); n * k];
 
-    for i in 0..side {
-        for j in 0..side {
-            b_transposed[j * side + i] = b[i * side + j];
+    for i in 0..k {
+        for j in 0..n {
+            b_transposed[j * k + i] = b[i * n + j];
         }
     }
 
     // multiplicação das matrizes tendo enconta a transposta de B
-    for (i, a_row) in a.chunks_exact(side).enumerate() {
+    for (i, a_row) in a.chunks_exact(k).enumerate() {
         // itera sobre as linhas de A
-        let res_row = &mut res[i * side..(i + 1) * side]; // extrai a row _i_ do [res]
+        let res_row = &mut res[i * n..(i + 1) * n]; // extrai a row _i_ do [res]
 
-        for k in 0..side {
+        for kk in 0..k {
             // itera sobre colunas de A / linhas de B.
-            let a_val = a_row[k]; // a[i][k];
-            let b_trans_row = &b_transposed[k * side..(k + 1) * side]; // b[k][j] -> linha k do b (pre-transposto)
+            let a_val = a_row[kk]; // a[i][k];
 
             // multiplicação e acumulação dos valores selecionados
-            res_row
-                .iter_mut()
-                .zip(b_trans_row.iter())
-                .for_each(|(r, &b_val)| {
-                    *r += a_val * b_val;
-                });
+            for (j, r) in res_row.iter_mut().enumerate() {
+                *r = *r + a_val * b_transposed[j * k + kk];
+            }
         }
     }
 
     Some(res)
 }
 
-pub fn unsafe_mul_line(a: &[f64], b: &[f64]) -> Option<Vec<f64>> {
-    if a.len() != b.len() {
+/// `a` is `m x k`, `b` is `k x n`, and the result is `m x n`.
+pub fn unsafe_mul_line<T>(a: &[T], b: &[T], m: usize, k: usize, n: usize) -> Option<Vec<T>>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    if a.len() != m * k || b.len() != k * n {
         return None;
     }
 
-    let length = a.len();
-    let side_f64 = (length as f64).sqrt();
+    let mut res = vec![T::zero(); m * n];
 
-    if side_f64.fract() != 0.0 {
-        return None;
-    }
-
-    let side = side_f64 as usize;
-    let mut res = vec![0.0; length];
-
-    // Transpose matrix B to improve cache locality
-    let mut b_transposed = vec![0.0; length];
-
-    for i in 0..side {
-        for j in 0..side {
+    // Transpose B (k x n) into (n x k) to improve cache locality.
+    let mut b_transposed = vec![T::zero(); n * k];
+    for i in 0..k {
+        for j in 0..n {
             unsafe {
-                *b_transposed.get_unchecked_mut(j * side + i) = *b.get_unchecked(i * side + j);
+                *b_transposed.get_unchecked_mut(j * k + i) = *b.get_unchecked(i * n + j);
             }
         }
     }
 
-    // Matrix multiplication using transposed B
-    for i in 0..side {
-        let res_row = &mut res[i * side..(i + 1) * side];
-        let a_row = &a[i * side..(i + 1) * side];
+    for i in 0..m {
+        let res_row = &mut res[i * n..(i + 1) * n];
+        let a_row = &a[i * k..(i + 1) * k];
 
-        for k in 0..side {
-            let a_val = unsafe { *a_row.get_unchecked(k) };
+        for kk in 0..k {
+            let a_val = unsafe { *a_row.get_unchecked(kk) };
 
-            for j in 0..side {
+            for j in 0..n {
                 unsafe {
-                    *res_row.get_unchecked_mut(j) +=
-                        a_val * *b_transposed.get_unchecked(k * side + j);
+                    *res_row.get_unchecked_mut(j) =
+                        *res_row.get_unchecked(j) + a_val * *b_transposed.get_unchecked(j * k + kk);
                 }
             }
         }
@@ -93,37 +80,40 @@ pub fn unsafe_mul_line(a: &[f64], b: &[f64]) -> Option<Vec<f64>> {
     Some(res)
 }
 
-pub fn final_mul_block(a: &[f64], b: &[f64], bk_size: usize) -> Option<Vec<f64>> {
-    if a.len() != b.len() {
+/// `a` is `m x k`, `b` is `k x n`, and the result is `m x n`.
+pub fn final_mul_block<T>(
+    a: &[T],
+    b: &[T],
+    m: usize,
+    k: usize,
+    n: usize,
+    bk_size: usize,
+) -> Option<Vec<T>>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    if a.len() != m * k || b.len() != k * n {
         return None;
     }
 
-    let length = a.len();
-    let side_f64 = (length as f64).sqrt();
-    if side_f64.fract() != 0.0 {
-        return None;
-    }
-
-    let side = side_f64 as usize;
-    let mut res = vec![0.0; length];
+    let mut res = vec![T::zero(); m * n];
 
-    let mut b_transposed = vec![0.0; length];
-    for i in 0..side {
-        for j in 0..side {
-            b_transposed[j * side + i] = b[i * side + j];
+    let mut b_transposed = vec![T::zero(); n * k];
+    for i in 0..k {
+        for j in 0..n {
+            b_transposed[j * k + i] = b[i * n + j];
         }
     }
 
-    for ii in (0..side).step_by(bk_size) {
-        for jj in (0..side).step_by(bk_size) {
-            for kk in (0..side).step_by(bk_size) {
-                for i in ii..(ii + bk_size).min(side) {
-                    for k in kk..(kk + bk_size).min(side) {
-                        let a_val = a[i * side + k];
-                        let b_row = &b_transposed[k * side..(k + 1) * side];
-                        let res_row = &mut res[i * side..(i + 1) * side];
-                        for j in jj..(jj + bk_size).min(side) {
-                            res_row[j] += a_val * b_row[j];
+    for ii in (0..m).step_by(bk_size) {
+        for jj in (0..n).step_by(bk_size) {
+            for kk in (0..k).step_by(bk_size) {
+                for i in ii..(ii + bk_size).min(m) {
+                    let res_row = &mut res[i * n..(i + 1) * n];
+                    for kb in kk..(kk + bk_size).min(k) {
+                        let a_val = a[i * k + kb];
+                        for j in jj..(jj + bk_size).min(n) {
+                            res_row[j] = res_row[j] + a_val * b_transposed[j * k + kb];
                         }
                     }
                 }
@@ -133,3 +123,55 @@ pub fn final_mul_block(a: &[f64], b: &[f64], bk_size: usize) -> Option<Vec<f64>>
 
     Some(res)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_mul(a: &[i32], b: &[i32], m: usize, k: usize, n: usize) -> Vec<i32> {
+        let mut res = vec![0; m * n];
+        for i in 0..m {
+            for kk in 0..k {
+                for j in 0..n {
+                    res[i * n + j] += a[i * k + kk] * b[kk * n + j];
+                }
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn final_mul_line_matches_naive_for_rectangular_shapes() {
+        let (m, k, n) = (2, 3, 4);
+        let a: Vec<i32> = (0..m * k).map(|x| x as i32 + 1).collect();
+        let b: Vec<i32> = (0..k * n).map(|x| x as i32 * 2 - 3).collect();
+        let expected = naive_mul(&a, &b, m, k, n);
+        let actual = final_mul_line(&a, &b, m, k, n).expect("dimensions line up");
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn final_mul_block_matches_naive_for_rectangular_shapes() {
+        let (m, k, n) = (5, 7, 3);
+        let a: Vec<i32> = (0..m * k).map(|x| x as i32 - 4).collect();
+        let b: Vec<i32> = (0..k * n).map(|x| x as i32 + 2).collect();
+        let expected = naive_mul(&a, &b, m, k, n);
+        let actual = final_mul_block(&a, &b, m, k, n, 2).expect("dimensions line up");
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn final_mul_line_rejects_mismatched_dimensions() {
+        assert!(final_mul_line(&[1, 2], &[1, 2, 3], 1, 2, 1).is_none());
+    }
+
+    #[test]
+    fn unsafe_mul_line_matches_naive_for_rectangular_shapes() {
+        let (m, k, n) = (2, 3, 4);
+        let a: Vec<i32> = (0..m * k).map(|x| x as i32 + 1).collect();
+        let b: Vec<i32> = (0..k * n).map(|x| x as i32 * 2 - 3).collect();
+        let expected = naive_mul(&a, &b, m, k, n);
+        let actual = unsafe_mul_line(&a, &b, m, k, n).expect("dimensions line up");
+        assert_eq!(actual, expected);
+    }
+}
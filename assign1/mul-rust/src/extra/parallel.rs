@@ -1,17 +1,32 @@
+use num_traits::Zero;
 use std::fs::File;
 use std::io::{self, Write};
+use std::ops::{Add, Mul};
 use std::time::Instant;
 
-pub fn basic_matrix_multiplication(a: &[f64], b: &[f64], side: usize) -> Option<Vec<f64>> {
-    if a.len() != side * side || b.len() != side * side {
+#[path = "../optimized.rs"]
+mod optimized;
+#[path = "optimized.rs"]
+mod extra_optimized;
+#[path = "../sparse.rs"]
+mod sparse;
+#[path = "../strassen.rs"]
+mod strassen;
+
+/// `a` is `m x k`, `b` is `k x n`, and the result is `m x n`.
+pub fn basic_matrix_multiplication<T>(a: &[T], b: &[T], m: usize, k: usize, n: usize) -> Option<Vec<T>>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    if a.len() != m * k || b.len() != k * n {
         return None;
     }
-    let mut result = vec![0.0; side * side];
-    for i in 0..side {
-        for k in 0..side {
-            let a_val = a[i * side + k];
-            for j in 0..side {
-                result[i * side + j] += a_val * b[k * side + j];
+    let mut result = vec![T::zero(); m * n];
+    for i in 0..m {
+        for kk in 0..k {
+            let a_val = a[i * k + kk];
+            for j in 0..n {
+                result[i * n + j] = result[i * n + j] + a_val * b[kk * n + j];
             }
         }
     }
@@ -74,6 +89,14 @@ fn generate_matrix(size: usize) -> Vec<f64> {
     (0..size * size).map(|i| (i % 10) as f64).collect()
 }
 
+/// Builds a `size x size` matrix with only roughly `fill_fraction` of its entries non-zero.
+fn generate_sparse_matrix(size: usize, fill_fraction: f64) -> Vec<f64> {
+    let stride = (1.0 / fill_fraction).round().max(1.0) as usize;
+    (0..size * size)
+        .map(|i| if i % stride == 0 { (i % 10) as f64 + 1.0 } else { 0.0 })
+        .collect()
+}
+
 fn main() {
     // Create and initialize the CSV file
     let mut csv_file = File::create("results.csv").expect("Failed to create CSV file.");
@@ -113,7 +136,7 @@ fn run_basic_line(csv_file: &mut File) {
 
         // Basic multiplication
         let start = Instant::now();
-        let res1 = basic_matrix_multiplication(&a, &b, size)
+        let res1 = basic_matrix_multiplication(&a, &b, size, size, size)
             .expect("Basic multiplication failed unexpectedly.");
         let duration1 = start.elapsed();
         println!("Basic multiplication completed in: {:.2?}", duration1);
@@ -173,5 +196,97 @@ fn run_block(csv_file: &mut File) {
             writeln!(csv_file, "Block,{},Block,{},{:.4}", size, block_size, avg_secs)
                 .expect("Failed to write block multiplication result to CSV.");
         }
+
+        // Strassen should beat block-oriented multiplication at these sizes.
+        let start = Instant::now();
+        let _ = strassen::strassen_mul(&a, &b, size, 256)
+            .expect("Strassen multiplication failed unexpectedly.");
+        let strassen_secs = start.elapsed().as_secs_f64();
+        println!("  Strassen (cutoff 256): {:.4} sec", strassen_secs);
+        writeln!(csv_file, "Block,{},Strassen,256,{:.4}", size, strassen_secs)
+            .expect("Failed to write Strassen multiplication result to CSV.");
+
+        // The `extra`-local final_mul_line/final_mul_block/unsafe_mul_line kernels
+        // live alongside the shared ones; benchmark them here too so they stay
+        // compiled, tested, and under comparison rather than a second dead copy.
+        let start = Instant::now();
+        let _ = extra_optimized::final_mul_line(&a, &b, size, size, size)
+            .expect("final_mul_line failed unexpectedly.");
+        let final_line_secs = start.elapsed().as_secs_f64();
+        println!("  final_mul_line: {:.4} sec", final_line_secs);
+        writeln!(csv_file, "Block,{},FinalMulLine,,{:.4}", size, final_line_secs)
+            .expect("Failed to write final_mul_line result to CSV.");
+
+        let start = Instant::now();
+        let _ = extra_optimized::final_mul_block(&a, &b, size, size, size, 256)
+            .expect("final_mul_block failed unexpectedly.");
+        let final_block_secs = start.elapsed().as_secs_f64();
+        println!("  final_mul_block: {:.4} sec", final_block_secs);
+        writeln!(csv_file, "Block,{},FinalMulBlock,256,{:.4}", size, final_block_secs)
+            .expect("Failed to write final_mul_block result to CSV.");
+
+        let start = Instant::now();
+        let _ = extra_optimized::unsafe_mul_line(&a, &b, size, size, size)
+            .expect("unsafe_mul_line failed unexpectedly.");
+        let unsafe_line_secs = start.elapsed().as_secs_f64();
+        println!("  unsafe_mul_line: {:.4} sec", unsafe_line_secs);
+        writeln!(csv_file, "Block,{},UnsafeMulLine,,{:.4}", size, unsafe_line_secs)
+            .expect("Failed to write unsafe_mul_line result to CSV.");
+
+        // Let the density threshold decide whether a sparse matrix should go through CSR SpGEMM.
+        let sparse_a = generate_sparse_matrix(size, 0.01);
+        let sparse_b = generate_sparse_matrix(size, 0.01);
+        let nnz_a = sparse_a.iter().filter(|&&x| x != 0.0).count();
+        let density_a = sparse::density(nnz_a, size, size);
+        if sparse::should_use_sparse(density_a) {
+            let coo_a = sparse::convert_dense_coo(&sparse_a, size, size).unwrap();
+            let csr_a = sparse::convert_coo_csr(&coo_a);
+            let csc_a = sparse::convert_coo_csc(&coo_a);
+            debug_assert_eq!(sparse::convert_csr_dense(&csr_a), sparse_a);
+            debug_assert_eq!(sparse::convert_csc_dense(&csc_a), sparse_a);
+
+            let csr_b = sparse::convert_coo_csr(&sparse::convert_dense_coo(&sparse_b, size, size).unwrap());
+
+            let x: Vec<f64> = (0..size).map(|i| (i % 5) as f64).collect();
+            let _ = sparse::csr_mul_dense(&csr_a, &x).expect("Sparse SpMV failed unexpectedly.");
+
+            let start = Instant::now();
+            let _ =
+                sparse::csr_mul_csr(&csr_a, &csr_b).expect("Sparse multiplication failed unexpectedly.");
+            let sparse_secs = start.elapsed().as_secs_f64();
+            println!(
+                "  Sparse CSR x CSR (density {:.3}): {:.4} sec",
+                density_a, sparse_secs
+            );
+            writeln!(csv_file, "Block,{},Sparse,,{:.4}", size, sparse_secs)
+                .expect("Failed to write sparse multiplication result to CSV.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_mul(a: &[i32], b: &[i32], m: usize, k: usize, n: usize) -> Vec<i32> {
+        let mut res = vec![0; m * n];
+        for i in 0..m {
+            for kk in 0..k {
+                for j in 0..n {
+                    res[i * n + j] += a[i * k + kk] * b[kk * n + j];
+                }
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn basic_matrix_multiplication_matches_naive_for_rectangular_shapes() {
+        let (m, k, n) = (2, 3, 4);
+        let a: Vec<i32> = (0..m * k).map(|x| x as i32 + 1).collect();
+        let b: Vec<i32> = (0..k * n).map(|x| x as i32 * 2 - 3).collect();
+        let expected = naive_mul(&a, &b, m, k, n);
+        let actual = basic_matrix_multiplication(&a, &b, m, k, n).expect("dimensions line up");
+        assert_eq!(actual, expected);
     }
 }
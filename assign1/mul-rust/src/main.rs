@@ -1,87 +1,174 @@
+// `simd_mul_line` uses `std::simd`, which is nightly-only; only enable the
+// unstable crate feature when the `simd` Cargo feature is turned on, so a
+// stable-toolchain build of the rest of the crate is unaffected.
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+mod optimized;
+mod repl;
+#[cfg(feature = "simd")]
+mod simd;
+mod sparse;
+mod strassen;
+
+use num_traits::{NumCast, Zero};
 use std::env;
+use std::ops::{Add, Mul};
 use std::time::Instant;
 
-/// A square matrix with dimensions `side x side`
+/// A `rows x cols` row-major matrix, generic over the scalar type.
 #[derive(Debug, Clone)]
-pub struct Matrix {
-    side: usize,
-    data: Vec<f64>,
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
 }
 
-impl Matrix {
-    /// Creates a new matrix with preset values (cyclic values from 0 to 9)
-    pub fn new(side: usize) -> Self {
-        let data = (0..side * side).map(|i| (i % 10) as f64).collect();
-        Matrix { side, data }
-    }
+/// The `f64` matrix used by the CLI, as a named alias of `Matrix<f64>`.
+pub type MatrixF64 = Matrix<f64>;
 
-    /// Constructs a matrix from a vector of data. Returns None if the length is not side².
-    pub fn from_vec(side: usize, data: Vec<f64>) -> Option<Self> {
-        if data.len() != side * side {
+impl<T> Matrix<T>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    /// Constructs a matrix from a vector of data. Returns None if the length is not `rows * cols`.
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Option<Self> {
+        if data.len() != rows * cols {
             None
         } else {
-            Some(Matrix { side, data })
+            Some(Matrix { rows, cols, data })
         }
     }
 
-    /// Basic matrix multiplication using triple nested loops.
-    pub fn multiply_basic(&self, other: &Matrix) -> Option<Matrix> {
-        if self.side != other.side {
+    /// Transposes `other` (`k x n`) into an `n x k` buffer for cache-friendly access.
+    fn transpose_other(other: &Matrix<T>) -> Vec<T> {
+        let (k, n) = (other.rows, other.cols);
+        let mut transposed = vec![T::zero(); k * n];
+        for i in 0..k {
+            for j in 0..n {
+                transposed[j * k + i] = other.data[i * n + j];
+            }
+        }
+        transposed
+    }
+
+    /// Returns the transpose of this matrix.
+    pub fn transpose(&self) -> Matrix<T> {
+        Matrix {
+            rows: self.cols,
+            cols: self.rows,
+            data: Self::transpose_other(self),
+        }
+    }
+
+    /// Basic matrix multiplication using triple nested loops. `self` is `m x k`,
+    /// `other` is `k x n`, and the result is `m x n`.
+    pub fn multiply_basic(&self, other: &Matrix<T>) -> Option<Matrix<T>> {
+        if self.cols != other.rows {
             return None;
         }
-        let side = self.side;
-        let mut result = vec![0.0; side * side];
-        for i in 0..side {
-            for k in 0..side {
-                let a_val = self.data[i * side + k];
-                for j in 0..side {
-                    result[i * side + j] += a_val * other.data[k * side + j];
+        let (m, k, n) = (self.rows, self.cols, other.cols);
+        let mut result = vec![T::zero(); m * n];
+        for i in 0..m {
+            for kk in 0..k {
+                let a_val = self.data[i * k + kk];
+                for j in 0..n {
+                    result[i * n + j] = result[i * n + j] + a_val * other.data[kk * n + j];
                 }
             }
         }
-        Some(Matrix { side, data: result })
+        Some(Matrix {
+            rows: m,
+            cols: n,
+            data: result,
+        })
     }
 
-    /// Line-based matrix multiplication (structure identical to basic multiplication).
-    pub fn multiply_line(&self, other: &Matrix) -> Option<Matrix> {
-        if self.side != other.side {
+    /// Line-based matrix multiplication, using a transposed `other` for cache locality.
+    pub fn multiply_line(&self, other: &Matrix<T>) -> Option<Matrix<T>> {
+        if self.cols != other.rows {
             return None;
         }
-        let side = self.side;
-        let mut result = vec![0.0; side * side];
-        for i in 0..side {
-            for k in 0..side {
-                let a_val = self.data[i * side + k];
-                for j in 0..side {
-                    result[i * side + j] += a_val * other.data[k * side + j];
+        let (m, k, n) = (self.rows, self.cols, other.cols);
+        let other_transposed = Self::transpose_other(other);
+        let mut result = vec![T::zero(); m * n];
+        for i in 0..m {
+            let res_row = &mut result[i * n..(i + 1) * n];
+            for kk in 0..k {
+                let a_val = self.data[i * k + kk];
+                for (j, r) in res_row.iter_mut().enumerate() {
+                    *r = *r + a_val * other_transposed[j * k + kk];
                 }
             }
         }
-        Some(Matrix { side, data: result })
+        Some(Matrix {
+            rows: m,
+            cols: n,
+            data: result,
+        })
     }
 
     /// Block matrix multiplication. The matrix is divided into blocks of the given block size.
-    pub fn multiply_block(&self, other: &Matrix, block_size: usize) -> Option<Matrix> {
-        if self.side != other.side {
+    pub fn multiply_block(&self, other: &Matrix<T>, block_size: usize) -> Option<Matrix<T>> {
+        if self.cols != other.rows {
             return None;
         }
-        let side = self.side;
-        let mut result = vec![0.0; side * side];
-        for ii in (0..side).step_by(block_size) {
-            for jj in (0..side).step_by(block_size) {
-                for kk in (0..side).step_by(block_size) {
-                    for i in ii..(ii + block_size).min(side) {
-                        for k in kk..(kk + block_size).min(side) {
-                            let a_val = self.data[i * side + k];
-                            for j in jj..(jj + block_size).min(side) {
-                                result[i * side + j] += a_val * other.data[k * side + j];
+        let (m, k, n) = (self.rows, self.cols, other.cols);
+        let other_transposed = Self::transpose_other(other);
+        let mut result = vec![T::zero(); m * n];
+        for ii in (0..m).step_by(block_size) {
+            for jj in (0..n).step_by(block_size) {
+                for kk in (0..k).step_by(block_size) {
+                    for i in ii..(ii + block_size).min(m) {
+                        for kb in kk..(kk + block_size).min(k) {
+                            let a_val = self.data[i * k + kb];
+                            for j in jj..(jj + block_size).min(n) {
+                                result[i * n + j] = result[i * n + j]
+                                    + a_val * other_transposed[j * k + kb];
                             }
                         }
                     }
                 }
             }
         }
-        Some(Matrix { side, data: result })
+        Some(Matrix {
+            rows: m,
+            cols: n,
+            data: result,
+        })
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T> + NumCast,
+{
+    /// Creates a new square matrix with preset values (cyclic values from 0 to 9)
+    pub fn new(side: usize) -> Self {
+        let data = (0..side * side)
+            .map(|i| T::from(i % 10).expect("cyclic seed value must fit the scalar type"))
+            .collect();
+        Matrix {
+            rows: side,
+            cols: side,
+            data,
+        }
+    }
+}
+
+#[cfg(feature = "simd")]
+impl Matrix<f64> {
+    /// Line-based multiplication using the SIMD inner kernel (`simd::simd_mul_line`).
+    pub fn multiply_simd(&self, other: &Matrix<f64>) -> Option<Matrix<f64>> {
+        if self.cols != other.rows {
+            return None;
+        }
+        let (m, k, n) = (self.rows, self.cols, other.cols);
+        let data = simd::simd_mul_line(&self.data, &other.data, m, k, n)?;
+        Some(Matrix {
+            rows: m,
+            cols: n,
+            data,
+        })
     }
 }
 
@@ -90,6 +177,8 @@ enum Mode {
     Normal,
     Line,
     Block(usize), // Contains the block size
+    #[cfg(feature = "simd")]
+    Simd,
 }
 
 impl Mode {
@@ -99,6 +188,8 @@ impl Mode {
             "n" => Some(Mode::Normal),
             "l" => Some(Mode::Line),
             "b" => maybe_block_size.map(Mode::Block),
+            #[cfg(feature = "simd")]
+            "s" => Some(Mode::Simd),
             _ => None,
         }
     }
@@ -106,10 +197,14 @@ impl Mode {
 
 fn main() {
     let args: Vec<String> = env::args().collect();
+    if args.len() >= 2 && args[1].to_lowercase() == "repl" {
+        repl::run();
+        return;
+    }
     if args.len() < 3 {
         eprintln!(
-            "Usage:\n  For normal and line modes: {} <mode: n|l> <matrix_size> [iterations]\n  For block mode: {} b <matrix_size> [iterations] <block_size>",
-            args[0], args[0]
+            "Usage:\n  For normal, line, and simd modes: {} <mode: n|l|s> <matrix_size> [iterations]\n  For block mode: {} b <matrix_size> [iterations] <block_size>\n  For the interactive calculator: {} repl",
+            args[0], args[0], args[0]
         );
         std::process::exit(1);
     }
@@ -136,15 +231,15 @@ fn main() {
     } else {
         Mode::from_args(&args[1], None).unwrap_or_else(|| {
             eprintln!(
-                "Invalid mode. Use 'n' for normal, 'l' for line, or 'b' for block multiplication."
+                "Invalid mode. Use 'n' for normal, 'l' for line, 'b' for block, or (with the `simd` feature enabled) 's' for SIMD multiplication."
             );
             std::process::exit(1);
         })
     };
 
     // Generate matrices; note that we use Matrix::new so that generation is outside the timing.
-    let a = Matrix::new(matrix_size);
-    let b = Matrix::new(matrix_size);
+    let a = MatrixF64::new(matrix_size);
+    let b = MatrixF64::new(matrix_size);
 
     // CSV header for output.
     println!("iteration,time_sec");
@@ -155,8 +250,42 @@ fn main() {
             Mode::Normal => a.multiply_basic(&b),
             Mode::Line => a.multiply_line(&b),
             Mode::Block(bs) => a.multiply_block(&b, bs),
+            #[cfg(feature = "simd")]
+            Mode::Simd => a.multiply_simd(&b),
         };
         let duration_sec = start.elapsed().as_nanos() as f64 / 1_000_000_000.0;
         println!("{},{}", iter, duration_sec);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiply_line_matches_basic_for_rectangular_shapes() {
+        let a = Matrix::from_vec(2, 3, vec![1, 2, 3, 4, 5, 6]).unwrap();
+        let b = Matrix::from_vec(3, 4, vec![7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18]).unwrap();
+        let expected = a.multiply_basic(&b).unwrap();
+        let actual = a.multiply_line(&b).unwrap();
+        assert_eq!(actual.data, expected.data);
+        assert_eq!((actual.rows, actual.cols), (2, 4));
+    }
+
+    #[test]
+    fn multiply_block_matches_basic_for_rectangular_shapes() {
+        let a = Matrix::from_vec(5, 7, (0..35).collect()).unwrap();
+        let b = Matrix::from_vec(7, 3, (0..21).map(|x| x - 10).collect()).unwrap();
+        let expected = a.multiply_basic(&b).unwrap();
+        let actual = a.multiply_block(&b, 2).unwrap();
+        assert_eq!(actual.data, expected.data);
+    }
+
+    #[test]
+    fn transpose_round_trips_for_rectangular_shapes() {
+        let a = Matrix::from_vec(2, 3, vec![1, 2, 3, 4, 5, 6]).unwrap();
+        let at = a.transpose();
+        assert_eq!((at.rows, at.cols), (3, 2));
+        assert_eq!(at.data, vec![1, 4, 2, 5, 3, 6]);
+    }
+}
@@ -1,4 +1,4 @@
-// WORKS FOR SQUARE MATRICES
+// Works for any m x k times k x n product; only requires a's columns to match b's rows.
 pub fn on_mult_line(a: &Vec<Vec<f64>>, b: &Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
     let rows_a = a.len();
     let rows_b = b.len();
@@ -24,29 +24,21 @@ pub fn on_mult_line(a: &Vec<Vec<f64>>, b: &Vec<Vec<f64>>) -> Option<Vec<Vec<f64>
     Some(res)
 }
 
-pub fn on_mult_line_flat(a: &Vec<f64>, b: &Vec<f64>) -> Option<Vec<f64>> {
-    if a.len() != b.len() {
+// a is m x k, b is k x n, result is m x n
+pub fn on_mult_line_flat(a: &Vec<f64>, b: &Vec<f64>, m: usize, k: usize, n: usize) -> Option<Vec<f64>> {
+    if a.len() != m * k || b.len() != k * n {
         return None;
     }
-    let length = a.len();
 
-    let side_f64 = (length as f64).sqrt();
-
-    if side_f64.fract() != 0.0 {
-        return None;
-    }
-
-    let side = side_f64 as usize;
-
-    let mut res: Vec<f64> = vec![0.0; length];
+    let mut res: Vec<f64> = vec![0.0; m * n];
 
     let mut temp;
 
-    for i in 0..side {
-        for k in 0..side {
-            temp = a[i * side + k];
-            for j in 0..side {
-                res[i * side + j] += temp * b[k * side + j];
+    for i in 0..m {
+        for kk in 0..k {
+            temp = a[i * k + kk];
+            for j in 0..n {
+                res[i * n + j] += temp * b[kk * n + j];
             }
         }
     }
@@ -1,78 +1,147 @@
-pub fn final_mul_line(a: &[f64], b: &[f64]) -> Option<Vec<f64>> {
-    assert_eq!(a.len(), b.len(), "Matrix dimensions do not match");
+use num_traits::Zero;
+use std::ops::{Add, Mul};
+
+/// `a` is `m x k`, `b` is `k x n`, and the result is `m x n`.
+pub fn final_mul_line<T>(a: &[T], b: &[T], m: usize, k: usize, n: usize) -> Option<Vec<T>>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    assert_eq!(a.len(), m * k, "A's length does not match m * k");
+    assert_eq!(b.len(), k * n, "B's length does not match k * n");
+
+    let mut res = vec![T::zero(); m * n];
+
+    // Transpose B (k x n) into (n x k); row j of b_transposed is B's column j.
+    let mut b_transposed = vec![T::zero(); n * k];
+    for i in 0..k {
+        for j in 0..n {
+            b_transposed[j * k + i] = b[i * n + j];
+        }
+    }
 
-    let length = a.len();
-    let side_f64 = (length as f64).sqrt();
+    for (i, a_row) in a.chunks_exact(k).enumerate() {
+        let res_row = &mut res[i * n..(i + 1) * n];
 
-    assert!(side_f64.fract() == 0.0, "Matrix must be a perfect square");
+        for kk in 0..k {
+            let a_val = a_row[kk];
 
-    let side = side_f64 as usize;
-    let mut res = vec![0.0; length];
+            for (j, r) in res_row.iter_mut().enumerate() {
+                *r = *r + a_val * b_transposed[j * k + kk];
+            }
+        }
+    }
 
-    let mut b_transposed = vec![0.0; length];
+    Some(res)
+}
 
-    for i in 0..side {
-        for j in 0..side {
-            b_transposed[j * side + i] = b[i * side + j];
+/// `a` is `m x k`, `b` is `k x n`, and the result is `m x n`.
+pub fn final_mul_block<T>(
+    a: &[T],
+    b: &[T],
+    m: usize,
+    k: usize,
+    n: usize,
+    bk_size: usize,
+) -> Option<Vec<T>>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    assert_eq!(a.len(), m * k, "A's length does not match m * k");
+    assert_eq!(b.len(), k * n, "B's length does not match k * n");
+
+    let mut res = vec![T::zero(); m * n];
+
+    let mut b_transposed = vec![T::zero(); n * k];
+    for i in 0..k {
+        for j in 0..n {
+            b_transposed[j * k + i] = b[i * n + j];
         }
     }
 
-    for (i, a_row) in a.chunks_exact(side).enumerate() {
-        let res_row = &mut res[i * side..(i + 1) * side];
-
-        for k in 0..side {
-            let a_val = a_row[k];
-            let b_trans_row = &b_transposed[k * side..(k + 1) * side];
+    debug_assert!(bk_size > 0, "Block size must be greater than zero");
 
-            res_row
-                .iter_mut()
-                .zip(b_trans_row.iter())
-                .for_each(|(r, &b_val)| {
-                    *r += a_val * b_val;
-                });
+    for ii in (0..m).step_by(bk_size) {
+        for jj in (0..n).step_by(bk_size) {
+            for kk in (0..k).step_by(bk_size) {
+                for i in ii..(ii + bk_size).min(m) {
+                    let res_row = &mut res[i * n..(i + 1) * n];
+                    for kb in kk..(kk + bk_size).min(k) {
+                        let a_val = a[i * k + kb];
+                        for j in jj..(jj + bk_size).min(n) {
+                            res_row[j] = res_row[j] + a_val * b_transposed[j * k + kb];
+                        }
+                    }
+                }
+            }
         }
     }
 
     Some(res)
 }
 
-pub fn final_mul_block(a: &[f64], b: &[f64], bk_size: usize) -> Option<Vec<f64>> {
-    assert_eq!(a.len(), b.len(), "Matrix dimensions do not match");
-
-    let length = a.len();
-    let side_f64 = (length as f64).sqrt();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    assert!(side_f64.fract() == 0.0, "Matrix must be a perfect square");
-
-    let side = side_f64 as usize;
-    let mut res = vec![0.0; length];
+    fn naive_mul(a: &[i32], b: &[i32], m: usize, k: usize, n: usize) -> Vec<i32> {
+        let mut res = vec![0; m * n];
+        for i in 0..m {
+            for kk in 0..k {
+                for j in 0..n {
+                    res[i * n + j] += a[i * k + kk] * b[kk * n + j];
+                }
+            }
+        }
+        res
+    }
 
-    let mut b_transposed = vec![0.0; length];
+    #[test]
+    fn final_mul_line_matches_naive_for_rectangular_shapes() {
+        let (m, k, n) = (2, 3, 4);
+        let a: Vec<i32> = (0..m * k).map(|x| x as i32 + 1).collect();
+        let b: Vec<i32> = (0..k * n).map(|x| x as i32 * 2 - 3).collect();
+        let expected = naive_mul(&a, &b, m, k, n);
+        let actual = final_mul_line(&a, &b, m, k, n).expect("dimensions line up");
+        assert_eq!(actual, expected);
+    }
 
-    for i in 0..side {
-        for j in 0..side {
-            b_transposed[j * side + i] = b[i * side + j];
-        }
+    #[test]
+    fn final_mul_block_matches_naive_for_rectangular_shapes() {
+        let (m, k, n) = (5, 7, 3);
+        let a: Vec<i32> = (0..m * k).map(|x| x as i32 - 4).collect();
+        let b: Vec<i32> = (0..k * n).map(|x| x as i32 + 2).collect();
+        let expected = naive_mul(&a, &b, m, k, n);
+        let actual = final_mul_block(&a, &b, m, k, n, 2).expect("dimensions line up");
+        assert_eq!(actual, expected);
     }
 
-    debug_assert!(bk_size > 0, "Block size must be greater than zero");
+    #[test]
+    #[should_panic(expected = "B's length does not match k * n")]
+    fn final_mul_line_panics_on_mismatched_dimensions() {
+        let _ = final_mul_line(&[1, 2], &[1, 2, 3], 1, 2, 1);
+    }
 
-    for ii in (0..side).step_by(bk_size) {
-        for jj in (0..side).step_by(bk_size) {
-            for kk in (0..side).step_by(bk_size) {
-                for i in ii..(ii + bk_size).min(side) {
-                    let res_row = &mut res[i * side..(i + 1) * side];
-                    for k in kk..(kk + bk_size).min(side) {
-                        let a_val = a[i * side + k];
-                        let b_row = &b_transposed[k * side..(k + 1) * side];
-                        for j in jj..(jj + bk_size).min(side) {
-                            res_row[j] += a_val * b_row[j];
-                        }
-                    }
+    fn naive_mul_f32(a: &[f32], b: &[f32], m: usize, k: usize, n: usize) -> Vec<f32> {
+        let mut res = vec![0.0; m * n];
+        for i in 0..m {
+            for kk in 0..k {
+                for j in 0..n {
+                    res[i * n + j] += a[i * k + kk] * b[kk * n + j];
                 }
             }
         }
+        res
     }
 
-    Some(res)
+    #[test]
+    fn final_mul_line_matches_naive_for_f32() {
+        let (m, k, n) = (2, 3, 4);
+        let a: Vec<f32> = (0..m * k).map(|x| x as f32 * 0.5 - 1.0).collect();
+        let b: Vec<f32> = (0..k * n).map(|x| x as f32 * 0.25 + 2.0).collect();
+        let expected = naive_mul_f32(&a, &b, m, k, n);
+        let actual = final_mul_line(&a, &b, m, k, n).expect("dimensions line up");
+        for (e, act) in expected.iter().zip(actual.iter()) {
+            assert!((e - act).abs() < 1e-4, "{e} vs {act}");
+        }
+    }
 }
@@ -1,75 +1,77 @@
+use num_traits::Zero;
 use rayon::prelude::*;
+use std::ops::{Add, Mul};
 
-pub fn final_mul_line_parallel(a: &[f64], b: &[f64]) -> Option<Vec<f64>> {
-    assert_eq!(a.len(), b.len(), "Matrix dimensions do not match");
+/// `a` is `m x k`, `b` is `k x n`, and the result is `m x n`.
+pub fn final_mul_line_parallel<T>(a: &[T], b: &[T], m: usize, k: usize, n: usize) -> Option<Vec<T>>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T> + Send + Sync,
+{
+    assert_eq!(a.len(), m * k, "A's length does not match m * k");
+    assert_eq!(b.len(), k * n, "B's length does not match k * n");
 
-    let length = a.len();
-    let side_f64 = (length as f64).sqrt();
+    let mut res = vec![T::zero(); m * n];
 
-    assert!(side_f64.fract() == 0.0, "Matrix must be a perfect square");
+    let mut b_transposed = vec![T::zero(); n * k];
 
-    let side = side_f64 as usize;
-    let mut res = vec![0.0; length];
-
-    let mut b_transposed = vec![0.0; length];
-
-    for i in 0..side {
-        for j in 0..side {
-            b_transposed[j * side + i] = b[i * side + j];
+    for i in 0..k {
+        for j in 0..n {
+            b_transposed[j * k + i] = b[i * n + j];
         }
     }
     //cada thread calcula uma row
-    res.par_chunks_exact_mut(side)
+    res.par_chunks_exact_mut(n)
         .enumerate()
         .for_each(|(i, res_row)| {
-            let a_row = &a[i * side..(i + 1) * side];
-            for k in 0..side {
-                let a_val = a_row[k]; // a[i][k]
-                let b_trans_row = &b_transposed[k * side..(k + 1) * side];
-                res_row
-                    .iter_mut()
-                    .zip(b_trans_row.iter())
-                    .for_each(|(r, &b_val)| {
-                        *r += a_val * b_val;
-                    });
+            let a_row = &a[i * k..(i + 1) * k];
+            for kk in 0..k {
+                let a_val = a_row[kk]; // a[i][k]
+                for (j, r) in res_row.iter_mut().enumerate() {
+                    *r = *r + a_val * b_transposed[j * k + kk];
+                }
             }
         });
 
     Some(res)
 }
 
-pub fn parallel_mul_block(a: &[f64], b: &[f64], bk_size: usize) -> Option<Vec<f64>> {
-    assert_eq!(a.len(), b.len(), "Matrix dimensions do not match");
-
-    let length = a.len();
-    let side_f64 = (length as f64).sqrt();
-
-    assert!(side_f64.fract() == 0.0, "Matrix must be a perfect square");
-
-    let side = side_f64 as usize;
-    let mut res = vec![0.0; length];
-
-    let mut b_transposed = vec![0.0; length];
-
-    for i in 0..side {
-        for j in 0..side {
-            b_transposed[j * side + i] = b[i * side + j];
+/// `a` is `m x k`, `b` is `k x n`, and the result is `m x n`.
+pub fn parallel_mul_block<T>(
+    a: &[T],
+    b: &[T],
+    m: usize,
+    k: usize,
+    n: usize,
+    bk_size: usize,
+) -> Option<Vec<T>>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T> + Send + Sync,
+{
+    assert_eq!(a.len(), m * k, "A's length does not match m * k");
+    assert_eq!(b.len(), k * n, "B's length does not match k * n");
+
+    let mut res = vec![T::zero(); m * n];
+
+    let mut b_transposed = vec![T::zero(); n * k];
+
+    for i in 0..k {
+        for j in 0..n {
+            b_transposed[j * k + i] = b[i * n + j];
         }
     }
 
     debug_assert!(bk_size > 0, "Block size must be greater than zero");
 
-    res.par_chunks_mut(side)
+    res.par_chunks_mut(n)
         .enumerate()
         .for_each(|(ii, res_chunk)| {
-            for jj in (0..side).step_by(bk_size) {
-                for kk in (0..side).step_by(bk_size) {
-                    let i = ii * side;
-                    for k in kk..(kk + bk_size).min(side) {
-                        let a_val = a[i + k];
-                        let b_row = &b_transposed[k * side..(k + 1) * side];
-                        for j in jj..(jj + bk_size).min(side) {
-                            res_chunk[j] += a_val * b_row[j];
+            for jj in (0..n).step_by(bk_size) {
+                for kk in (0..k).step_by(bk_size) {
+                    let i = ii * k;
+                    for kb in kk..(kk + bk_size).min(k) {
+                        let a_val = a[i + kb];
+                        for j in jj..(jj + bk_size).min(n) {
+                            res_chunk[j] = res_chunk[j] + a_val * b_transposed[j * k + kb];
                         }
                     }
                 }
@@ -78,3 +80,40 @@ pub fn parallel_mul_block(a: &[f64], b: &[f64], bk_size: usize) -> Option<Vec<f6
 
     Some(res)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_mul(a: &[i32], b: &[i32], m: usize, k: usize, n: usize) -> Vec<i32> {
+        let mut res = vec![0; m * n];
+        for i in 0..m {
+            for kk in 0..k {
+                for j in 0..n {
+                    res[i * n + j] += a[i * k + kk] * b[kk * n + j];
+                }
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn final_mul_line_parallel_matches_naive_for_rectangular_shapes() {
+        let (m, k, n) = (6, 4, 5);
+        let a: Vec<i32> = (0..m * k).map(|x| x as i32 - 2).collect();
+        let b: Vec<i32> = (0..k * n).map(|x| x as i32 * 2 + 1).collect();
+        let expected = naive_mul(&a, &b, m, k, n);
+        let actual = final_mul_line_parallel(&a, &b, m, k, n).expect("dimensions line up");
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parallel_mul_block_matches_naive_for_rectangular_shapes() {
+        let (m, k, n) = (5, 7, 3);
+        let a: Vec<i32> = (0..m * k).map(|x| x as i32 - 4).collect();
+        let b: Vec<i32> = (0..k * n).map(|x| x as i32 + 2).collect();
+        let expected = naive_mul(&a, &b, m, k, n);
+        let actual = parallel_mul_block(&a, &b, m, k, n, 2).expect("dimensions line up");
+        assert_eq!(actual, expected);
+    }
+}
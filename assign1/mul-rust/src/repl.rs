@@ -0,0 +1,399 @@
+//! A small matrix expression REPL: `A = new(3)`, `C = A * B`,
+//! `D = block(A, B, 256)`, `E = A'` (transpose), `time(A * B)`.
+//!
+//! Expressions are lexed into tokens, parsed into an AST, lowered into a
+//! tiny bytecode, and run on a stack-based `Vm` whose environment maps
+//! variable names to `Matrix<f64>` values.
+
+use crate::Matrix;
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::time::Instant;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(usize),
+    Star,
+    Quote,
+    Equal,
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn lex(line: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            ' ' | '\t' => i += 1,
+            '*' => {
+                tokens.push(Token::Star);
+                i += 1;
+            }
+            '\'' => {
+                tokens.push(Token::Quote);
+                i += 1;
+            }
+            '=' => {
+                tokens.push(Token::Equal);
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            c if c.is_ascii_digit() => {
+                let start = i;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+                let num: String = chars[start..i].iter().collect();
+                let value = num
+                    .parse()
+                    .map_err(|_| format!("`{num}` does not fit in a number literal"))?;
+                tokens.push(Token::Number(value));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let ident: String = chars[start..i].iter().collect();
+                tokens.push(Token::Ident(ident));
+            }
+            _ => i += 1,
+        }
+    }
+    Ok(tokens)
+}
+
+/// Expression AST produced by the parser.
+#[derive(Debug, Clone)]
+enum Expr {
+    Var(String),
+    NumberLit(usize),
+    Transpose(Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+/// A parsed statement: either `name = expr` or a bare expression to evaluate and print.
+enum Stmt {
+    Assign(String, Expr),
+    Eval(Expr),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Parser { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn parse_stmt(&mut self) -> Option<Stmt> {
+        if let (Some(Token::Ident(name)), Some(Token::Equal)) =
+            (self.tokens.get(self.pos), self.tokens.get(self.pos + 1))
+        {
+            let name = name.clone();
+            self.pos += 2;
+            let expr = self.parse_expr()?;
+            return Some(Stmt::Assign(name, expr));
+        }
+        Some(Stmt::Eval(self.parse_expr()?))
+    }
+
+    fn parse_expr(&mut self) -> Option<Expr> {
+        let mut lhs = self.parse_postfix()?;
+        while matches!(self.peek(), Some(Token::Star)) {
+            self.next();
+            let rhs = self.parse_postfix()?;
+            lhs = Expr::Mul(Box::new(lhs), Box::new(rhs));
+        }
+        Some(lhs)
+    }
+
+    fn parse_postfix(&mut self) -> Option<Expr> {
+        let mut expr = self.parse_primary()?;
+        while matches!(self.peek(), Some(Token::Quote)) {
+            self.next();
+            expr = Expr::Transpose(Box::new(expr));
+        }
+        Some(expr)
+    }
+
+    fn parse_primary(&mut self) -> Option<Expr> {
+        match self.next()? {
+            Token::Number(n) => Some(Expr::NumberLit(n)),
+            Token::LParen => {
+                let expr = self.parse_expr()?;
+                if !matches!(self.next(), Some(Token::RParen)) {
+                    return None;
+                }
+                Some(expr)
+            }
+            Token::Ident(name) => {
+                if matches!(self.peek(), Some(Token::LParen)) {
+                    self.next();
+                    let mut args = Vec::new();
+                    if !matches!(self.peek(), Some(Token::RParen)) {
+                        args.push(self.parse_expr()?);
+                        while matches!(self.peek(), Some(Token::Comma)) {
+                            self.next();
+                            args.push(self.parse_expr()?);
+                        }
+                    }
+                    if !matches!(self.next(), Some(Token::RParen)) {
+                        return None;
+                    }
+                    Some(Expr::Call(name, args))
+                } else {
+                    Some(Expr::Var(name))
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Bytecode ops the `Vm` executes against its matrix stack.
+#[derive(Debug, Clone)]
+enum Op {
+    Load(String),
+    New(usize),
+    MulLine,
+    MulBlock(usize),
+    Transpose,
+    Store(String),
+    Print,
+    TimeStart,
+    TimeEnd,
+}
+
+fn compile_expr(expr: &Expr, ops: &mut Vec<Op>) -> Result<(), String> {
+    match expr {
+        Expr::Var(name) => ops.push(Op::Load(name.clone())),
+        Expr::NumberLit(_) => return Err("a number cannot be evaluated as a matrix".to_string()),
+        Expr::Transpose(inner) => {
+            compile_expr(inner, ops)?;
+            ops.push(Op::Transpose);
+        }
+        Expr::Mul(lhs, rhs) => {
+            compile_expr(lhs, ops)?;
+            compile_expr(rhs, ops)?;
+            ops.push(Op::MulLine);
+        }
+        Expr::Call(name, args) => match name.as_str() {
+            "block" => {
+                let [a, b, size] = &args[..] else {
+                    return Err("block(A, B, block_size) takes exactly 3 arguments".to_string());
+                };
+                let Expr::NumberLit(size) = size else {
+                    return Err("block's third argument must be a number".to_string());
+                };
+                compile_expr(a, ops)?;
+                compile_expr(b, ops)?;
+                ops.push(Op::MulBlock(*size));
+            }
+            "time" => {
+                let [inner] = &args[..] else {
+                    return Err("time(expr) takes exactly 1 argument".to_string());
+                };
+                ops.push(Op::TimeStart);
+                compile_expr(inner, ops)?;
+                ops.push(Op::TimeEnd);
+            }
+            "new" => {
+                let [side] = &args[..] else {
+                    return Err("new(side) takes exactly 1 argument".to_string());
+                };
+                let Expr::NumberLit(side) = side else {
+                    return Err("new's argument must be a number".to_string());
+                };
+                ops.push(Op::New(*side));
+            }
+            other => return Err(format!("unknown builtin `{other}`")),
+        },
+    }
+    Ok(())
+}
+
+fn compile_stmt(stmt: &Stmt) -> Result<Vec<Op>, String> {
+    let mut ops = Vec::new();
+    match stmt {
+        Stmt::Assign(name, expr) => {
+            compile_expr(expr, &mut ops)?;
+            ops.push(Op::Store(name.clone()));
+        }
+        Stmt::Eval(expr) => {
+            compile_expr(expr, &mut ops)?;
+            ops.push(Op::Print);
+        }
+    }
+    Ok(ops)
+}
+
+/// Holds the REPL's variable environment and executes compiled bytecode.
+pub struct Vm {
+    env: HashMap<String, Matrix<f64>>,
+}
+
+impl Vm {
+    pub fn new() -> Self {
+        Vm {
+            env: HashMap::new(),
+        }
+    }
+
+    /// Binds a variable in the environment, e.g. to seed `A` and `B` before a session.
+    pub fn set(&mut self, name: &str, matrix: Matrix<f64>) {
+        self.env.insert(name.to_string(), matrix);
+    }
+
+    fn exec(&mut self, ops: &[Op]) -> Result<(), String> {
+        let mut stack: Vec<Matrix<f64>> = Vec::new();
+        let mut timers: Vec<Instant> = Vec::new();
+
+        for op in ops {
+            match op {
+                Op::Load(name) => {
+                    let matrix = self
+                        .env
+                        .get(name)
+                        .ok_or_else(|| format!("undefined variable `{name}`"))?
+                        .clone();
+                    stack.push(matrix);
+                }
+                Op::New(side) => stack.push(Matrix::new(*side)),
+                Op::Transpose => {
+                    let matrix = stack.pop().ok_or("stack underflow")?;
+                    stack.push(matrix.transpose());
+                }
+                Op::MulLine => {
+                    let rhs = stack.pop().ok_or("stack underflow")?;
+                    let lhs = stack.pop().ok_or("stack underflow")?;
+                    let result = lhs
+                        .multiply_line(&rhs)
+                        .ok_or("matrix dimensions do not match for `*`")?;
+                    stack.push(result);
+                }
+                Op::MulBlock(block_size) => {
+                    let rhs = stack.pop().ok_or("stack underflow")?;
+                    let lhs = stack.pop().ok_or("stack underflow")?;
+                    let result = lhs
+                        .multiply_block(&rhs, *block_size)
+                        .ok_or("matrix dimensions do not match for `block`")?;
+                    stack.push(result);
+                }
+                Op::Store(name) => {
+                    let matrix = stack.last().ok_or("stack underflow")?.clone();
+                    self.env.insert(name.clone(), matrix);
+                }
+                Op::Print => {
+                    let matrix = stack.pop().ok_or("stack underflow")?;
+                    println!("{:?}", matrix);
+                }
+                Op::TimeStart => timers.push(Instant::now()),
+                Op::TimeEnd => {
+                    let start = timers.pop().ok_or("time() was not opened")?;
+                    println!("{:.6} sec", start.elapsed().as_secs_f64());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses and runs a single line of REPL input.
+    pub fn run_line(&mut self, line: &str) -> Result<(), String> {
+        let tokens = lex(line)?;
+        if tokens.is_empty() {
+            return Ok(());
+        }
+        let stmt = Parser::new(tokens)
+            .parse_stmt()
+            .ok_or("could not parse expression")?;
+        let ops = compile_stmt(&stmt)?;
+        self.exec(&ops)
+    }
+}
+
+impl Default for Vm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs the interactive REPL loop against stdin/stdout until EOF or `exit`.
+pub fn run() {
+    let mut vm = Vm::new();
+    let stdin = io::stdin();
+    loop {
+        print!("> ");
+        io::stdout().flush().ok();
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line == "exit" || line == "quit" {
+            break;
+        }
+        if let Err(err) = vm.run_line(line) {
+            eprintln!("error: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_line_reports_an_error_instead_of_panicking_on_an_oversized_number_literal() {
+        let mut vm = Vm::new();
+        let huge = format!("{}0", usize::MAX);
+        let err = vm.run_line(&huge).expect_err("the literal overflows usize");
+        assert!(err.contains("does not fit"));
+    }
+
+    #[test]
+    fn run_line_supports_a_full_multiply_transpose_time_example() {
+        let mut vm = Vm::new();
+        vm.run_line("A = new(3)").unwrap();
+        vm.run_line("B = new(3)").unwrap();
+        vm.run_line("C = A * B").unwrap();
+        vm.run_line("D = block(A, B, 2)").unwrap();
+        vm.run_line("E = A'").unwrap();
+        vm.run_line("time(A * B)").unwrap();
+        assert_eq!(vm.env["C"].data, vm.env["D"].data);
+        assert_eq!(vm.env["E"].data, vm.env["A"].transpose().data);
+    }
+}
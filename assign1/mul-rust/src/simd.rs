@@ -0,0 +1,89 @@
+//! Explicit SIMD inner kernel for the line multiplier, using portable SIMD.
+//!
+//! `unsafe_mul_line` already transposes B for contiguous access and uses
+//! `get_unchecked`, but the inner accumulation loop is still scalar. Here each
+//! output cell is a dot product between a row of `A` and a row of the
+//! transposed `B`, both length `k`, accumulated in lanes of 8 via
+//! `std::simd::f64x8` with a scalar tail for `k % LANES != 0`.
+
+use std::simd::num::SimdFloat;
+use std::simd::{f64x8, StdFloat};
+
+const LANES: usize = 8;
+
+/// `a` is `m x k`, `b` is `k x n`, and the result is `m x n`.
+pub fn simd_mul_line(a: &[f64], b: &[f64], m: usize, k: usize, n: usize) -> Option<Vec<f64>> {
+    if a.len() != m * k || b.len() != k * n {
+        return None;
+    }
+
+    let mut res = vec![0.0; m * n];
+
+    // Transpose B (k x n) into (n x k); row j of b_transposed is B's column j,
+    // which lines up with a row of A for a contiguous dot product.
+    let mut b_transposed = vec![0.0; n * k];
+    for i in 0..k {
+        for j in 0..n {
+            b_transposed[j * k + i] = b[i * n + j];
+        }
+    }
+
+    let simd_chunks = k / LANES;
+    let simd_len = simd_chunks * LANES;
+
+    for (i, a_row) in a.chunks_exact(k).enumerate() {
+        let res_row = &mut res[i * n..(i + 1) * n];
+
+        for (j, r) in res_row.iter_mut().enumerate() {
+            let b_col = &b_transposed[j * k..(j + 1) * k];
+
+            let mut acc = f64x8::splat(0.0);
+            for c in 0..simd_chunks {
+                let off = c * LANES;
+                let a_chunk = f64x8::from_slice(&a_row[off..off + LANES]);
+                let b_chunk = f64x8::from_slice(&b_col[off..off + LANES]);
+                acc = a_chunk.mul_add(b_chunk, acc);
+            }
+            let mut sum = acc.reduce_sum();
+
+            // Scalar tail for the lanes that don't fill a full f64x8.
+            for kk in simd_len..k {
+                sum += a_row[kk] * b_col[kk];
+            }
+
+            *r = sum;
+        }
+    }
+
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_mul(a: &[f64], b: &[f64], m: usize, k: usize, n: usize) -> Vec<f64> {
+        let mut res = vec![0.0; m * n];
+        for i in 0..m {
+            for kk in 0..k {
+                for j in 0..n {
+                    res[i * n + j] += a[i * k + kk] * b[kk * n + j];
+                }
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn simd_mul_line_matches_naive_for_rectangular_shapes() {
+        for &(m, k, n) in &[(2, 3, 4), (5, 9, 7), (3, 16, 3), (1, 1, 1), (4, 17, 5)] {
+            let a: Vec<f64> = (0..m * k).map(|x| x as f64 - 3.0).collect();
+            let b: Vec<f64> = (0..k * n).map(|x| x as f64 * 0.5 + 1.0).collect();
+            let expected = naive_mul(&a, &b, m, k, n);
+            let actual = simd_mul_line(&a, &b, m, k, n).unwrap();
+            for (e, act) in expected.iter().zip(actual.iter()) {
+                assert!((e - act).abs() < 1e-9, "m={m} k={k} n={n}: {e} vs {act}");
+            }
+        }
+    }
+}
@@ -0,0 +1,364 @@
+//! Sparse matrix storage formats (COO/CSR/CSC) and dense<->sparse conversions.
+//!
+//! These mirror the converters nalgebra's `sparse` module provides: a matrix
+//! is built up as an unordered list of `(row, col, value)` triples (COO) and
+//! then compressed into CSR or CSC for efficient multiplication.
+
+use num_traits::Zero;
+use std::ops::{Add, Mul};
+
+/// Coordinate-format sparse matrix: an unordered list of `(row, col, value)` triples.
+#[derive(Debug, Clone)]
+pub struct CooMatrix<T> {
+    rows: usize,
+    cols: usize,
+    row_indices: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T: Copy> CooMatrix<T> {
+    /// Creates an empty `rows x cols` matrix with no entries.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        CooMatrix {
+            rows,
+            cols,
+            row_indices: Vec::new(),
+            col_indices: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Appends a single `(row, col, value)` entry. Returns `None` if out of bounds.
+    pub fn push(&mut self, row: usize, col: usize, value: T) -> Option<()> {
+        if row >= self.rows || col >= self.cols {
+            return None;
+        }
+        self.row_indices.push(row);
+        self.col_indices.push(col);
+        self.values.push(value);
+        Some(())
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+}
+
+/// Compressed Sparse Row matrix: `row_offsets` has length `rows + 1`, and the
+/// entries of row `i` live in `col_indices[row_offsets[i]..row_offsets[i + 1]]`
+/// (with matching `values`).
+#[derive(Debug, Clone)]
+pub struct CsrMatrix<T> {
+    rows: usize,
+    cols: usize,
+    row_offsets: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T: Copy> CsrMatrix<T> {
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+}
+
+/// Compressed Sparse Column matrix: the column-major mirror of `CsrMatrix`.
+/// `col_offsets` has length `cols + 1`, and the entries of column `j` live in
+/// `row_indices[col_offsets[j]..col_offsets[j + 1]]` (with matching `values`).
+#[derive(Debug, Clone)]
+pub struct CscMatrix<T> {
+    rows: usize,
+    cols: usize,
+    col_offsets: Vec<usize>,
+    row_indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T: Copy> CscMatrix<T> {
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+}
+
+/// Converts a dense row-major matrix into COO, skipping zero entries.
+pub fn convert_dense_coo<T>(dense: &[T], rows: usize, cols: usize) -> Option<CooMatrix<T>>
+where
+    T: Copy + Zero,
+{
+    if dense.len() != rows * cols {
+        return None;
+    }
+    let mut coo = CooMatrix::new(rows, cols);
+    for i in 0..rows {
+        for j in 0..cols {
+            let val = dense[i * cols + j];
+            if !val.is_zero() {
+                coo.push(i, j, val);
+            }
+        }
+    }
+    Some(coo)
+}
+
+/// Converts COO into CSR by sorting entries by `(row, col)` and compressing row offsets.
+pub fn convert_coo_csr<T>(coo: &CooMatrix<T>) -> CsrMatrix<T>
+where
+    T: Copy,
+{
+    let mut order: Vec<usize> = (0..coo.nnz()).collect();
+    order.sort_by_key(|&idx| (coo.row_indices[idx], coo.col_indices[idx]));
+
+    let mut row_offsets = vec![0usize; coo.rows + 1];
+    let mut col_indices = Vec::with_capacity(coo.nnz());
+    let mut values = Vec::with_capacity(coo.nnz());
+
+    for &idx in &order {
+        row_offsets[coo.row_indices[idx] + 1] += 1;
+        col_indices.push(coo.col_indices[idx]);
+        values.push(coo.values[idx]);
+    }
+    for i in 0..coo.rows {
+        row_offsets[i + 1] += row_offsets[i];
+    }
+
+    CsrMatrix {
+        rows: coo.rows,
+        cols: coo.cols,
+        row_offsets,
+        col_indices,
+        values,
+    }
+}
+
+/// Converts COO into CSC by sorting entries by `(col, row)` and compressing column offsets.
+pub fn convert_coo_csc<T>(coo: &CooMatrix<T>) -> CscMatrix<T>
+where
+    T: Copy,
+{
+    let mut order: Vec<usize> = (0..coo.nnz()).collect();
+    order.sort_by_key(|&idx| (coo.col_indices[idx], coo.row_indices[idx]));
+
+    let mut col_offsets = vec![0usize; coo.cols + 1];
+    let mut row_indices = Vec::with_capacity(coo.nnz());
+    let mut values = Vec::with_capacity(coo.nnz());
+
+    for &idx in &order {
+        col_offsets[coo.col_indices[idx] + 1] += 1;
+        row_indices.push(coo.row_indices[idx]);
+        values.push(coo.values[idx]);
+    }
+    for j in 0..coo.cols {
+        col_offsets[j + 1] += col_offsets[j];
+    }
+
+    CscMatrix {
+        rows: coo.rows,
+        cols: coo.cols,
+        col_offsets,
+        row_indices,
+        values,
+    }
+}
+
+/// Expands a CSR matrix back into a dense row-major `Vec<T>`.
+pub fn convert_csr_dense<T>(csr: &CsrMatrix<T>) -> Vec<T>
+where
+    T: Copy + Zero,
+{
+    let mut dense = vec![T::zero(); csr.rows * csr.cols];
+    for i in 0..csr.rows {
+        for k in csr.row_offsets[i]..csr.row_offsets[i + 1] {
+            dense[i * csr.cols + csr.col_indices[k]] = csr.values[k];
+        }
+    }
+    dense
+}
+
+/// Expands a CSC matrix back into a dense row-major `Vec<T>`.
+pub fn convert_csc_dense<T>(csc: &CscMatrix<T>) -> Vec<T>
+where
+    T: Copy + Zero,
+{
+    let mut dense = vec![T::zero(); csc.rows * csc.cols];
+    for j in 0..csc.cols {
+        for k in csc.col_offsets[j]..csc.col_offsets[j + 1] {
+            dense[csc.row_indices[k] * csc.cols + j] = csc.values[k];
+        }
+    }
+    dense
+}
+
+/// Sparse (CSR) times dense vector: `y = A * x`. `x` must have `A.cols` entries.
+pub fn csr_mul_dense<T>(a: &CsrMatrix<T>, x: &[T]) -> Option<Vec<T>>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    if x.len() != a.cols {
+        return None;
+    }
+    let result = (0..a.rows)
+        .map(|i| {
+            (a.row_offsets[i]..a.row_offsets[i + 1])
+                .fold(T::zero(), |acc, k| acc + a.values[k] * x[a.col_indices[k]])
+        })
+        .collect();
+    Some(result)
+}
+
+/// Sparse times sparse: `C = A * B`, both CSR, returned as CSR.
+///
+/// This is the textbook row-by-row SpGEMM: for each row of `A`, accumulate
+/// `a_val * row(B)` into a dense scratch row, then compact the non-zeros.
+pub fn csr_mul_csr<T>(a: &CsrMatrix<T>, b: &CsrMatrix<T>) -> Option<CsrMatrix<T>>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    if a.cols != b.rows {
+        return None;
+    }
+
+    let mut row_offsets = vec![0usize; a.rows + 1];
+    let mut col_indices = Vec::new();
+    let mut values = Vec::new();
+    let mut scratch = vec![T::zero(); b.cols];
+    let mut seen = vec![false; b.cols];
+    let mut touched = Vec::new();
+
+    for i in 0..a.rows {
+        for k in a.row_offsets[i]..a.row_offsets[i + 1] {
+            let a_val = a.values[k];
+            let row_b = a.col_indices[k];
+            for kb in b.row_offsets[row_b]..b.row_offsets[row_b + 1] {
+                let col = b.col_indices[kb];
+                if !seen[col] {
+                    seen[col] = true;
+                    touched.push(col);
+                }
+                scratch[col] = scratch[col] + a_val * b.values[kb];
+            }
+        }
+        touched.sort_unstable();
+        for &col in &touched {
+            if !scratch[col].is_zero() {
+                col_indices.push(col);
+                values.push(scratch[col]);
+            }
+            scratch[col] = T::zero();
+            seen[col] = false;
+        }
+        row_offsets[i + 1] = col_indices.len();
+        touched.clear();
+    }
+
+    Some(CsrMatrix {
+        rows: a.rows,
+        cols: b.cols,
+        row_offsets,
+        col_indices,
+        values,
+    })
+}
+
+/// Fraction of non-zero entries among `rows * cols` total.
+pub fn density(nnz: usize, rows: usize, cols: usize) -> f64 {
+    nnz as f64 / (rows * cols) as f64
+}
+
+/// Whether a matrix with the given density should be treated as sparse.
+///
+/// Below roughly 10% non-zero, CSR storage and SpMV/SpGEMM beat dense
+/// kernels because the dense routines keep multiplying by zeros.
+pub fn should_use_sparse(density: f64) -> bool {
+    density < 0.1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_mul(a: &[i32], b: &[i32], m: usize, k: usize, n: usize) -> Vec<i32> {
+        let mut res = vec![0; m * n];
+        for i in 0..m {
+            for kk in 0..k {
+                for j in 0..n {
+                    res[i * n + j] += a[i * k + kk] * b[kk * n + j];
+                }
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn csr_round_trips_through_dense() {
+        let (rows, cols) = (3, 4);
+        let dense = vec![0, 1, 0, 0, 2, 0, 3, 0, 0, 0, 0, 4];
+        let coo = convert_dense_coo(&dense, rows, cols).unwrap();
+        let csr = convert_coo_csr(&coo);
+        assert_eq!(csr.nnz(), 4);
+        assert_eq!(convert_csr_dense(&csr), dense);
+    }
+
+    #[test]
+    fn csc_round_trips_through_dense() {
+        let (rows, cols) = (3, 4);
+        let dense = vec![0, 1, 0, 0, 2, 0, 3, 0, 0, 0, 0, 4];
+        let coo = convert_dense_coo(&dense, rows, cols).unwrap();
+        let csc = convert_coo_csc(&coo);
+        assert_eq!(csc.nnz(), 4);
+        assert_eq!(convert_csc_dense(&csc), dense);
+    }
+
+    #[test]
+    fn csr_mul_dense_matches_naive_matrix_vector_product() {
+        let (rows, cols) = (3, 4);
+        let dense = vec![0, 1, 0, 0, 2, 0, 3, 0, 0, 0, 0, 4];
+        let csr = convert_coo_csr(&convert_dense_coo(&dense, rows, cols).unwrap());
+        let x = vec![1, 2, 3, 4];
+        let expected = naive_mul(&dense, &x, rows, cols, 1);
+        let actual = csr_mul_dense(&csr, &x).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn csr_mul_csr_matches_naive_dense_product() {
+        let (m, k, n) = (3, 4, 2);
+        let a = vec![0, 1, 0, 0, 2, 0, 3, 0, 0, 0, 0, 4];
+        let b = vec![1, 0, 0, 2, 3, 0, 0, 4];
+        let csr_a = convert_coo_csr(&convert_dense_coo(&a, m, k).unwrap());
+        let csr_b = convert_coo_csr(&convert_dense_coo(&b, k, n).unwrap());
+        let expected = naive_mul(&a, &b, m, k, n);
+        let actual = convert_csr_dense(&csr_mul_csr(&csr_a, &csr_b).unwrap());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn should_use_sparse_applies_the_ten_percent_threshold() {
+        assert!(should_use_sparse(0.05));
+        assert!(!should_use_sparse(0.2));
+    }
+}
@@ -0,0 +1,163 @@
+//! Strassen's recursive divide-and-conquer multiplication for square matrices.
+//!
+//! Splitting each operand into four quadrants and combining them with 7
+//! recursive multiplications instead of 8 brings the asymptotic cost down
+//! from Θ(n³) to roughly Θ(n^2.807). At or below `cutoff` the recursion
+//! bottoms out into `final_mul_line`, since the transposed-B line multiplier
+//! beats the recursion overhead on small matrices.
+
+use crate::optimized::final_mul_line;
+use num_traits::Zero;
+use std::ops::{Add, Mul, Sub};
+
+/// Multiplies two `side x side` matrices with Strassen's algorithm, falling
+/// back to `final_mul_line` once the recursion reaches `cutoff` or below.
+/// A side that is not a power of two is padded with zeros once, up to the
+/// next power of two.
+pub fn strassen_mul<T>(a: &[T], b: &[T], side: usize, cutoff: usize) -> Option<Vec<T>>
+where
+    T: Copy + Zero + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    if a.len() != side * side || b.len() != side * side {
+        return None;
+    }
+
+    let padded_side = side.next_power_of_two();
+    if padded_side == side {
+        return Some(strassen_recursive(a, b, side, cutoff));
+    }
+
+    let a_padded = pad(a, side, padded_side);
+    let b_padded = pad(b, side, padded_side);
+    let c_padded = strassen_recursive(&a_padded, &b_padded, padded_side, cutoff);
+    Some(unpad(&c_padded, padded_side, side))
+}
+
+fn strassen_recursive<T>(a: &[T], b: &[T], side: usize, cutoff: usize) -> Vec<T>
+where
+    T: Copy + Zero + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    if side <= cutoff || side == 1 {
+        return final_mul_line(a, b, side, side, side).expect("square final_mul_line call");
+    }
+
+    let half = side / 2;
+    let (a11, a12, a21, a22) = split(a, side, half);
+    let (b11, b12, b21, b22) = split(b, side, half);
+
+    let m1 = strassen_recursive(&add(&a11, &a22), &add(&b11, &b22), half, cutoff);
+    let m2 = strassen_recursive(&add(&a21, &a22), &b11, half, cutoff);
+    let m3 = strassen_recursive(&a11, &sub(&b12, &b22), half, cutoff);
+    let m4 = strassen_recursive(&a22, &sub(&b21, &b11), half, cutoff);
+    let m5 = strassen_recursive(&add(&a11, &a12), &b22, half, cutoff);
+    let m6 = strassen_recursive(&sub(&a21, &a11), &add(&b11, &b12), half, cutoff);
+    let m7 = strassen_recursive(&sub(&a12, &a22), &add(&b21, &b22), half, cutoff);
+
+    let c11 = add(&sub(&add(&m1, &m4), &m5), &m7);
+    let c12 = add(&m3, &m5);
+    let c21 = add(&m2, &m4);
+    let c22 = add(&add(&sub(&m1, &m2), &m3), &m6);
+
+    join(&c11, &c12, &c21, &c22, half)
+}
+
+/// Splits a `side x side` matrix into its four `half x half` quadrants.
+fn split<T: Copy>(m: &[T], side: usize, half: usize) -> (Vec<T>, Vec<T>, Vec<T>, Vec<T>) {
+    let mut q11 = Vec::with_capacity(half * half);
+    let mut q12 = Vec::with_capacity(half * half);
+    let mut q21 = Vec::with_capacity(half * half);
+    let mut q22 = Vec::with_capacity(half * half);
+
+    for i in 0..half {
+        let row = &m[i * side..(i + 1) * side];
+        q11.extend_from_slice(&row[..half]);
+        q12.extend_from_slice(&row[half..]);
+    }
+    for i in half..side {
+        let row = &m[i * side..(i + 1) * side];
+        q21.extend_from_slice(&row[..half]);
+        q22.extend_from_slice(&row[half..]);
+    }
+
+    (q11, q12, q21, q22)
+}
+
+/// Joins four `half x half` quadrants back into a `2*half x 2*half` matrix.
+fn join<T: Copy + Zero>(
+    c11: &[T],
+    c12: &[T],
+    c21: &[T],
+    c22: &[T],
+    half: usize,
+) -> Vec<T> {
+    let side = half * 2;
+    let mut result = vec![T::zero(); side * side];
+    for i in 0..half {
+        result[i * side..i * side + half].copy_from_slice(&c11[i * half..(i + 1) * half]);
+        result[i * side + half..(i + 1) * side].copy_from_slice(&c12[i * half..(i + 1) * half]);
+    }
+    for i in 0..half {
+        let row = half + i;
+        result[row * side..row * side + half].copy_from_slice(&c21[i * half..(i + 1) * half]);
+        result[row * side + half..(row + 1) * side]
+            .copy_from_slice(&c22[i * half..(i + 1) * half]);
+    }
+    result
+}
+
+fn add<T: Copy + Add<Output = T>>(a: &[T], b: &[T]) -> Vec<T> {
+    a.iter().zip(b.iter()).map(|(&x, &y)| x + y).collect()
+}
+
+fn sub<T: Copy + Sub<Output = T>>(a: &[T], b: &[T]) -> Vec<T> {
+    a.iter().zip(b.iter()).map(|(&x, &y)| x - y).collect()
+}
+
+/// Zero-pads a `side x side` matrix up to `padded_side x padded_side`.
+fn pad<T: Copy + Zero>(m: &[T], side: usize, padded_side: usize) -> Vec<T> {
+    let mut result = vec![T::zero(); padded_side * padded_side];
+    for i in 0..side {
+        result[i * padded_side..i * padded_side + side]
+            .copy_from_slice(&m[i * side..(i + 1) * side]);
+    }
+    result
+}
+
+/// Crops a `padded_side x padded_side` matrix down to `side x side`.
+fn unpad<T: Copy>(m: &[T], padded_side: usize, side: usize) -> Vec<T> {
+    let mut result = Vec::with_capacity(side * side);
+    for i in 0..side {
+        result.extend_from_slice(&m[i * padded_side..i * padded_side + side]);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strassen_mul_matches_final_mul_line_for_power_of_two_side() {
+        let side = 4;
+        let a: Vec<i64> = (0..side * side).map(|x| x as i64 - 8).collect();
+        let b: Vec<i64> = (0..side * side).map(|x| x as i64 * 2 + 1).collect();
+        let expected = final_mul_line(&a, &b, side, side, side).unwrap();
+        let actual = strassen_mul(&a, &b, side, 1).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn strassen_mul_matches_final_mul_line_for_non_power_of_two_side() {
+        let side = 5;
+        let a: Vec<i64> = (0..side * side).map(|x| x as i64 + 3).collect();
+        let b: Vec<i64> = (0..side * side).map(|x| x as i64 - 2).collect();
+        let expected = final_mul_line(&a, &b, side, side, side).unwrap();
+        let actual = strassen_mul(&a, &b, side, 2).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn strassen_mul_rejects_mismatched_lengths() {
+        assert!(strassen_mul(&[1, 2, 3], &[1, 2, 3, 4], 2, 1).is_none());
+    }
+}
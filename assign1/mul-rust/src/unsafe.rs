@@ -1,41 +1,40 @@
-pub fn unsafe_mul_line(a: &[f64], b: &[f64]) -> Option<Vec<f64>> {
-    if a.len() != b.len() {
-        return None;
-    }
-
-    let length = a.len();
-    let side_f64 = (length as f64).sqrt();
+use num_traits::Zero;
+use std::ops::{Add, Mul};
 
-    if side_f64.fract() != 0.0 {
+/// `a` is `m x k`, `b` is `k x n`, and the result is `m x n`.
+pub fn unsafe_mul_line<T>(a: &[T], b: &[T], m: usize, k: usize, n: usize) -> Option<Vec<T>>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    if a.len() != m * k || b.len() != k * n {
         return None;
     }
 
-    let side = side_f64 as usize;
-    let mut res = vec![0.0; length];
+    let mut res = vec![T::zero(); m * n];
 
-    // Transpose matrix B to improve cache locality
-    let mut b_transposed = vec![0.0; length];
+    // Transpose matrix B (k x n) into (n x k); row j of b_transposed is B's column j.
+    let mut b_transposed = vec![T::zero(); n * k];
 
-    for i in 0..side {
-        for j in 0..side {
+    for i in 0..k {
+        for j in 0..n {
             unsafe {
-                *b_transposed.get_unchecked_mut(j * side + i) = *b.get_unchecked(i * side + j);
+                *b_transposed.get_unchecked_mut(j * k + i) = *b.get_unchecked(i * n + j);
             }
         }
     }
 
     // Matrix multiplication using transposed B
-    for i in 0..side {
-        let res_row = &mut res[i * side..(i + 1) * side];
-        let a_row = &a[i * side..(i + 1) * side];
+    for i in 0..m {
+        let res_row = &mut res[i * n..(i + 1) * n];
+        let a_row = &a[i * k..(i + 1) * k];
 
-        for k in 0..side {
-            let a_val = unsafe { *a_row.get_unchecked(k) };
+        for kk in 0..k {
+            let a_val = unsafe { *a_row.get_unchecked(kk) };
 
-            for j in 0..side {
+            for j in 0..n {
                 unsafe {
-                    *res_row.get_unchecked_mut(j) +=
-                        a_val * *b_transposed.get_unchecked(k * side + j);
+                    *res_row.get_unchecked_mut(j) = *res_row.get_unchecked(j)
+                        + a_val * *b_transposed.get_unchecked(j * k + kk);
                 }
             }
         }
@@ -43,3 +42,30 @@ pub fn unsafe_mul_line(a: &[f64], b: &[f64]) -> Option<Vec<f64>> {
 
     Some(res)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_mul(a: &[i32], b: &[i32], m: usize, k: usize, n: usize) -> Vec<i32> {
+        let mut res = vec![0; m * n];
+        for i in 0..m {
+            for kk in 0..k {
+                for j in 0..n {
+                    res[i * n + j] += a[i * k + kk] * b[kk * n + j];
+                }
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn unsafe_mul_line_matches_naive_for_rectangular_shapes() {
+        let (m, k, n) = (4, 6, 2);
+        let a: Vec<i32> = (0..m * k).map(|x| x as i32 - 10).collect();
+        let b: Vec<i32> = (0..k * n).map(|x| x as i32 * 3 + 1).collect();
+        let expected = naive_mul(&a, &b, m, k, n);
+        let actual = unsafe_mul_line(&a, &b, m, k, n).expect("dimensions line up");
+        assert_eq!(actual, expected);
+    }
+}